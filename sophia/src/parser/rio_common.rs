@@ -1,31 +1,62 @@
-//! Common implementations for adapting [RIO](https://github.com/Tpt/rio/blob/master/turtle/src/turtle.rs) parsers.
+//! Common implementations for adapting [RIO](https://github.com/Tpt/rio/blob/master/turtle/src/turtle.rs)
+//! parsers and serializers.
 
 use std::result::Result as StdResult;
 
+use mownstr::MownStr;
+use rio_api::formatter::{QuadsFormatter, TriplesFormatter};
 use rio_api::model::*;
 use rio_api::parser::*;
 
 use crate::error::*;
 use crate::ns::xsd;
 use crate::quad::stream::*;
-use crate::term::{BoxTerm, RefTerm};
+use crate::quad::Quad;
+use crate::term::iri::Iri;
+use crate::term::{BoxTerm, MownTerm, Term as SophiaTerm, TermData};
 use crate::triple::stream::*;
+use crate::triple::Triple;
 
-/// TripleSource / QuadSource adapter for RIO TripleParser
+/// TripleSource / QuadSource adapter for RIO TripleParser.
+///
+/// The optional base IRI (set via [`with_base`](#method.with_base)) is used
+/// to resolve relative IRIs encountered while parsing, e.g. in Turtle or
+/// TriG.
 pub enum RioSource<T, E> {
-    Parser(T),
+    Parser(T, Option<Iri<Box<str>>>),
     Error(Option<E>),
 }
 
 impl<T, E> From<StdResult<T, E>> for RioSource<T, E> {
     fn from(res: StdResult<T, E>) -> Self {
         match res {
-            Ok(parser) => RioSource::Parser(parser),
+            Ok(parser) => RioSource::Parser(parser, None),
             Err(error) => RioSource::Error(Some(error)),
         }
     }
 }
 
+impl<T, E> RioSource<T, E> {
+    /// Configure a base IRI against which relative IRIs encountered during
+    /// parsing (e.g. in Turtle or TriG) will be resolved.
+    ///
+    /// May fail if `iri` is not a valid (absolute) IRI.
+    /// Has no effect if this source has already failed.
+    pub fn with_base<U: AsRef<str>>(mut self, iri: U) -> Result<Self> {
+        if let RioSource::Parser(_, base) = &mut self {
+            let iri = Iri::<Box<str>>::new(iri.as_ref().to_string())?;
+            if !iri.is_absolute() {
+                return Err(Error::from(ErrorKind::ParserError(
+                    format!("base IRI is not absolute: {}", iri.value()),
+                    Location::Unknown,
+                )));
+            }
+            *base = Some(iri);
+        }
+        Ok(self)
+    }
+}
+
 impl<T, E> TripleSource for RioSource<T, E>
 where
     T: TriplesParser,
@@ -50,12 +81,12 @@ where
                     let location = Location::Unknown;
                     Err(Error::from(ErrorKind::ParserError(message, location)).into())
                 }),
-            RioSource::Parser(parser) => {
+            RioSource::Parser(parser, base) => {
                 parser.parse_all(&mut |t| -> Result<()> {
                     sink.feed(&[
-                        rio2refterm(t.subject.into()).unwrap(), // TODO handle error properly
-                        rio2refterm(t.predicate.into()).unwrap(), // TODO handle error properly
-                        rio2refterm(t.object).unwrap(),         // TODO handle error properly
+                        rio2refterm(t.subject.into(), base.as_ref())?,
+                        rio2refterm(t.predicate.into(), base.as_ref())?,
+                        rio2refterm(t.object, base.as_ref())?,
                     ])
                     .map_err(TS::Error::into)
                 })?;
@@ -89,19 +120,17 @@ where
                     let location = Location::Unknown;
                     Err(Error::from(ErrorKind::ParserError(message, location)).into())
                 }),
-            RioSource::Parser(parser) => {
+            RioSource::Parser(parser, base) => {
                 parser.parse_all(&mut |q| -> Result<()> {
                     sink.feed(&(
                         [
-                            rio2refterm(q.subject.into()).unwrap(), // TODO handle error properly
-                            rio2refterm(q.predicate.into()).unwrap(), // TODO handle error properly
-                            rio2refterm(q.object).unwrap(),         // TODO handle error properly
+                            rio2refterm(q.subject.into(), base.as_ref())?,
+                            rio2refterm(q.predicate.into(), base.as_ref())?,
+                            rio2refterm(q.object, base.as_ref())?,
                         ],
-                        if let Some(n) = q.graph_name {
-                            Some(rio2refterm(n.into()).unwrap()) // TODO handle error properly
-                        } else {
-                            None
-                        },
+                        q.graph_name
+                            .map(|n| rio2refterm(n.into(), base.as_ref()))
+                            .transpose()?,
                     ))
                     .map_err(TS::Error::into)
                 })?;
@@ -111,23 +140,820 @@ where
     }
 }
 
-/// Convert RIO term to Sophia term
-pub fn rio2refterm(t: Term) -> Result<RefTerm> {
+impl<T, E> RioSource<T, E>
+where
+    T: TriplesParser,
+    Error: From<T::Error>,
+    Error: From<E>,
+{
+    /// Turn this source into an iterator of triples, driving the
+    /// underlying RIO parser one [`parse_step`](TriplesParser::parse_step)
+    /// at a time.
+    ///
+    /// Unlike [`in_sink`](TripleSource::in_sink), which forces the whole
+    /// input through in one blocking pass, this lets callers stream
+    /// arbitrarily large inputs with bounded memory and stop early.
+    pub fn into_iter(self) -> RioSourceIter<T, E> {
+        RioSourceIter {
+            source: self,
+            pending: std::collections::VecDeque::new(),
+            done: false,
+        }
+    }
+}
+
+/// Iterator over the triples produced by a [`RioSource`],
+/// built by [`RioSource::into_iter`].
+pub struct RioSourceIter<T, E> {
+    source: RioSource<T, E>,
+    pending: std::collections::VecDeque<[BoxTerm; 3]>,
+    done: bool,
+}
+
+impl<T, E> Iterator for RioSourceIter<T, E>
+where
+    T: TriplesParser,
+    Error: From<T::Error>,
+    Error: From<E>,
+{
+    type Item = Result<[BoxTerm; 3]>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(triple) = self.pending.pop_front() {
+                return Some(Ok(triple));
+            }
+            if self.done {
+                return None;
+            }
+            let (parser, base) = match &mut self.source {
+                RioSource::Error(opt) => {
+                    self.done = true;
+                    return Some(Err(opt.take().map(Error::from).unwrap_or_else(|| {
+                        Error::from(ErrorKind::ParserError(
+                            "This parser has already failed".to_string(),
+                            Location::Unknown,
+                        ))
+                    })));
+                }
+                RioSource::Parser(parser, base) => (parser, &*base),
+            };
+            if parser.is_end() {
+                self.done = true;
+                continue;
+            }
+            let pending = &mut self.pending;
+            let step_result = parser.parse_step(&mut |t| -> Result<()> {
+                pending.push_back([
+                    rio2boxterm(t.subject.into(), base.as_ref())?,
+                    rio2boxterm(t.predicate.into(), base.as_ref())?,
+                    rio2boxterm(t.object, base.as_ref())?,
+                ]);
+                Ok(())
+            });
+            if let Err(e) = step_result {
+                self.done = true;
+                return Some(Err(Error::from(e)));
+            }
+        }
+    }
+}
+
+impl<T, E> RioSource<T, E>
+where
+    T: QuadsParser,
+    Error: From<T::Error>,
+    Error: From<E>,
+{
+    /// Turn this source into an iterator of quads,
+    /// analogous to [`into_iter`](RioSource::into_iter) for triples.
+    pub fn into_quad_iter(self) -> RioQuadSourceIter<T, E> {
+        RioQuadSourceIter {
+            source: self,
+            pending: std::collections::VecDeque::new(),
+            done: false,
+        }
+    }
+}
+
+/// Iterator over the quads produced by a [`RioSource`],
+/// built by [`RioSource::into_quad_iter`].
+pub struct RioQuadSourceIter<T, E> {
+    source: RioSource<T, E>,
+    pending: std::collections::VecDeque<([BoxTerm; 3], Option<BoxTerm>)>,
+    done: bool,
+}
+
+impl<T, E> Iterator for RioQuadSourceIter<T, E>
+where
+    T: QuadsParser,
+    Error: From<T::Error>,
+    Error: From<E>,
+{
+    type Item = Result<([BoxTerm; 3], Option<BoxTerm>)>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(quad) = self.pending.pop_front() {
+                return Some(Ok(quad));
+            }
+            if self.done {
+                return None;
+            }
+            let (parser, base) = match &mut self.source {
+                RioSource::Error(opt) => {
+                    self.done = true;
+                    return Some(Err(opt.take().map(Error::from).unwrap_or_else(|| {
+                        Error::from(ErrorKind::ParserError(
+                            "This parser has already failed".to_string(),
+                            Location::Unknown,
+                        ))
+                    })));
+                }
+                RioSource::Parser(parser, base) => (parser, &*base),
+            };
+            if parser.is_end() {
+                self.done = true;
+                continue;
+            }
+            let pending = &mut self.pending;
+            let step_result = parser.parse_step(&mut |q| -> Result<()> {
+                let graph_name = q
+                    .graph_name
+                    .map(|n| rio2boxterm(n.into(), base.as_ref()))
+                    .transpose()?;
+                pending.push_back((
+                    [
+                        rio2boxterm(q.subject.into(), base.as_ref())?,
+                        rio2boxterm(q.predicate.into(), base.as_ref())?,
+                        rio2boxterm(q.object, base.as_ref())?,
+                    ],
+                    graph_name,
+                ));
+                Ok(())
+            });
+            if let Err(e) = step_result {
+                self.done = true;
+                return Some(Err(Error::from(e)));
+            }
+        }
+    }
+}
+
+/// Resolve `raw` into an absolute IRI, joining it against `base` (RFC3987
+/// reference resolution) if it is relative.
+///
+/// Leaves already-absolute IRIs untouched. Fails if `raw` is relative and
+/// `base` is `None`, rather than silently producing a non-absolute term.
+fn resolve_iri<'a>(raw: &'a str, base: Option<&Iri<Box<str>>>) -> Result<Iri<MownStr<'a>>> {
+    let iri = Iri::<MownStr<'a>>::new(raw)?;
+    if iri.is_absolute() {
+        return Ok(iri);
+    }
+    match base {
+        Some(base) => Ok(base.resolve(raw)),
+        None => Err(Error::from(ErrorKind::ParserError(
+            format!("relative IRI '{}' encountered with no base IRI configured", raw),
+            Location::Unknown,
+        ))),
+    }
+}
+
+/// Convert RIO term to Sophia term, resolving relative IRIs against `base`.
+///
+/// Unlike a bare `unwrap`, conversion failures (e.g. an invalid datatype IRI
+/// or language tag, or a relative IRI with no `base`) are reported as an
+/// `Error` carrying the text of the offending RIO term, so that callers
+/// driving a `RioSource` can recover from (or report) a single malformed
+/// triple/quad instead of aborting. RIO's basic parser API does not expose
+/// a line/column for individual terms, so the location is
+/// `Location::Unknown`.
+pub fn rio2refterm<'a>(t: Term<'a>, base: Option<&Iri<Box<str>>>) -> Result<MownTerm<'a>> {
     use Literal::*;
+    let convert = || -> Result<MownTerm<'a>> {
+        match t {
+            Term::BlankNode(b) => MownTerm::new_bnode(b.id),
+            Term::NamedNode(n) => Ok(SophiaTerm::Iri(resolve_iri(n.iri, base)?)),
+            Term::Literal(Simple { value }) => MownTerm::new_literal_dt(value, xsd::string),
+            Term::Literal(LanguageTaggedString { value, language }) => {
+                MownTerm::new_literal_lang(value, language)
+            }
+            Term::Literal(Typed { value, datatype }) => {
+                MownTerm::new_literal_dt(value, resolve_iri(datatype.iri, base)?)
+            }
+        }
+    };
+    convert().map_err(|e| {
+        Error::from(ErrorKind::ParserError(
+            format!("can not convert RIO term {:?} to a Sophia term: {}", t, e),
+            Location::Unknown,
+        ))
+    })
+}
+
+/// Convert RIO term to Sophia term, resolving relative IRIs against `base`.
+pub fn rio2boxterm(t: Term, base: Option<&Iri<Box<str>>>) -> Result<BoxTerm> {
+    Ok(BoxTerm::from_with(&rio2refterm(t, base)?, Box::from))
+}
+
+/// Scratch space for the textual data of one RIO term.
+///
+/// RIO's borrowed types (`NamedNode<'a>`, `Literal<'a>`, ...) need somewhere
+/// to borrow their `&str`s from; this struct is that somewhere. It is
+/// reused across calls to [`RioTripleSink::feed`] and [`RioQuadSink::feed`]
+/// to avoid allocating afresh for every triple/quad.
+#[derive(Debug, Default)]
+struct TermBuf {
+    value: String,
+    tag: String,
+}
+
+impl TermBuf {
+    fn clear(&mut self) {
+        self.value.clear();
+        self.tag.clear();
+    }
+}
+
+/// Convert a Sophia term into a RIO [`Term`](rio_api::model::Term),
+/// writing its textual components into `buf` (which is cleared first),
+/// since the returned term borrows from it.
+///
+/// Fails if `t` is a `Variable`: RIO's data model only covers strict RDF.
+fn term2rio<'a, TD: TermData>(t: &SophiaTerm<TD>, buf: &'a mut TermBuf) -> Result<Term<'a>> {
+    buf.clear();
     match t {
-        Term::BlankNode(b) => RefTerm::new_bnode(b.id),
-        Term::NamedNode(n) => RefTerm::new_iri(n.iri),
-        Term::Literal(Simple { value }) => RefTerm::new_literal_dt(value, xsd::string),
-        Term::Literal(LanguageTaggedString { value, language }) => {
-            RefTerm::new_literal_lang(value, language)
+        SophiaTerm::Iri(iri) => {
+            buf.value.push_str(&iri.value());
+            Ok(Term::NamedNode(NamedNode { iri: &buf.value }))
+        }
+        SophiaTerm::BNode(bn) => {
+            buf.value.push_str(&bn.value());
+            Ok(Term::BlankNode(BlankNode { id: &buf.value }))
+        }
+        SophiaTerm::Literal(lit) => {
+            buf.value.push_str(&lit.value());
+            if let Some(lang) = lit.lang() {
+                buf.tag.push_str(lang);
+                Ok(Term::Literal(Literal::LanguageTaggedString {
+                    value: &buf.value,
+                    language: &buf.tag,
+                }))
+            } else if lit.dt().value().as_ref() == xsd::string {
+                Ok(Term::Literal(Literal::Simple { value: &buf.value }))
+            } else {
+                buf.tag.push_str(&lit.dt().value());
+                Ok(Term::Literal(Literal::Typed {
+                    value: &buf.value,
+                    datatype: NamedNode { iri: &buf.tag },
+                }))
+            }
+        }
+        SophiaTerm::Variable(_) => Err(Error::from(ErrorKind::ParserError(
+            format!(
+                "variable '{}' can not be converted to a RIO term: RIO only supports strict RDF",
+                t.value()
+            ),
+            Location::Unknown,
+        ))),
+    }
+}
+
+/// Convert a Sophia term into a RIO `Subject`, using `buf` as scratch space.
+///
+/// Fails if `t` is a `Literal` or a `Variable`,
+/// neither of which RIO allows in subject/predicate position.
+fn term2rio_subject<'a, TD: TermData>(
+    t: &SophiaTerm<TD>,
+    buf: &'a mut TermBuf,
+) -> Result<Subject<'a>> {
+    match term2rio(t, buf)? {
+        Term::NamedNode(n) => Ok(Subject::NamedNode(n)),
+        Term::BlankNode(b) => Ok(Subject::BlankNode(b)),
+        _ => Err(Error::from(ErrorKind::ParserError(
+            format!(
+                "term '{}' can not appear in subject/predicate position: RIO only supports strict RDF",
+                t.value()
+            ),
+            Location::Unknown,
+        ))),
+    }
+}
+
+/// Convert a Sophia term into a RIO `GraphName`, using `buf` as scratch space.
+fn term2rio_graphname<'a, TD: TermData>(
+    t: &SophiaTerm<TD>,
+    buf: &'a mut TermBuf,
+) -> Result<GraphName<'a>> {
+    match term2rio(t, buf)? {
+        Term::NamedNode(n) => Ok(GraphName::NamedNode(n)),
+        Term::BlankNode(b) => Ok(GraphName::BlankNode(b)),
+        _ => Err(Error::from(ErrorKind::ParserError(
+            format!(
+                "term '{}' can not be used as a graph name: RIO only supports strict RDF",
+                t.value()
+            ),
+            Location::Unknown,
+        ))),
+    }
+}
+
+/// `TripleSink` that feeds a RIO [`TriplesFormatter`],
+/// turning every triple fed to it into serialized output
+/// (Turtle, N-Triples, or whatever format `F` writes).
+pub struct RioTripleSink<F: TriplesFormatter> {
+    formatter: Option<F>,
+    buffers: [TermBuf; 3],
+}
+
+impl<F: TriplesFormatter> RioTripleSink<F> {
+    /// Build a new sink wrapping the given RIO formatter.
+    pub fn new(formatter: F) -> Self {
+        RioTripleSink {
+            formatter: Some(formatter),
+            buffers: Default::default(),
         }
-        Term::Literal(Typed { value, datatype }) => {
-            RefTerm::new_literal_dt(value, RefTerm::new_iri(datatype.iri)?)
+    }
+}
+
+impl<F> TripleSink for RioTripleSink<F>
+where
+    F: TriplesFormatter,
+    Error: From<F::Error>,
+{
+    type Outcome = F;
+    type Error = Error;
+
+    fn feed<T: Triple>(&mut self, t: &T) -> StdResult<(), Self::Error> {
+        let formatter = self.formatter.as_mut().ok_or_else(already_finished)?;
+        let [sbuf, pbuf, obuf] = &mut self.buffers;
+        let subject = term2rio_subject(t.s(), sbuf)?;
+        let predicate = match term2rio_subject(t.p(), pbuf)? {
+            Subject::NamedNode(n) => n,
+            _ => {
+                return Err(Error::from(ErrorKind::ParserError(
+                    format!("predicate '{}' must be an IRI", t.p().value()),
+                    Location::Unknown,
+                )))
+            }
+        };
+        let object = term2rio(t.o(), obuf)?;
+        Ok(formatter.format(&rio_api::model::Triple {
+            subject,
+            predicate,
+            object,
+        })?)
+    }
+
+    fn finish(&mut self) -> StdResult<Self::Outcome, Self::Error> {
+        self.formatter.take().ok_or_else(already_finished)
+    }
+}
+
+/// `QuadSink` that feeds a RIO [`QuadsFormatter`],
+/// turning every quad fed to it into serialized output
+/// (TriG, N-Quads, or whatever format `F` writes).
+pub struct RioQuadSink<F: QuadsFormatter> {
+    formatter: Option<F>,
+    buffers: [TermBuf; 4],
+}
+
+impl<F: QuadsFormatter> RioQuadSink<F> {
+    /// Build a new sink wrapping the given RIO formatter.
+    pub fn new(formatter: F) -> Self {
+        RioQuadSink {
+            formatter: Some(formatter),
+            buffers: Default::default(),
         }
     }
 }
 
-/// Convert RIO term to Sophia term
-pub fn rio2boxterm(t: Term) -> Result<BoxTerm> {
-    Ok(BoxTerm::from_with(&rio2refterm(t)?, Box::from))
+impl<F> QuadSink for RioQuadSink<F>
+where
+    F: QuadsFormatter,
+    Error: From<F::Error>,
+{
+    type Outcome = F;
+    type Error = Error;
+
+    fn feed<Q: Quad>(&mut self, q: &Q) -> StdResult<(), Self::Error> {
+        let formatter = self.formatter.as_mut().ok_or_else(already_finished)?;
+        let [sbuf, pbuf, obuf, gbuf] = &mut self.buffers;
+        let subject = term2rio_subject(q.s(), sbuf)?;
+        let predicate = match term2rio_subject(q.p(), pbuf)? {
+            Subject::NamedNode(n) => n,
+            _ => {
+                return Err(Error::from(ErrorKind::ParserError(
+                    format!("predicate '{}' must be an IRI", q.p().value()),
+                    Location::Unknown,
+                )))
+            }
+        };
+        let object = term2rio(q.o(), obuf)?;
+        let graph_name = q.g().map(|g| term2rio_graphname(g, gbuf)).transpose()?;
+        Ok(formatter.format(&rio_api::model::Quad {
+            subject,
+            predicate,
+            object,
+            graph_name,
+        })?)
+    }
+
+    fn finish(&mut self) -> StdResult<Self::Outcome, Self::Error> {
+        self.formatter.take().ok_or_else(already_finished)
+    }
+}
+
+/// Build the error returned when `feed` or `finish` is called on a sink
+/// whose formatter has already been handed back by a previous `finish`.
+fn already_finished() -> Error {
+    Error::from(ErrorKind::ParserError(
+        "this sink has already been finished".to_string(),
+        Location::Unknown,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct FakeTripleFormatter {
+        out: Vec<String>,
+    }
+
+    impl TriplesFormatter for FakeTripleFormatter {
+        type Error = Error;
+
+        fn format(&mut self, triple: &rio_api::model::Triple) -> StdResult<(), Self::Error> {
+            self.out.push(format!("{:?}", triple));
+            Ok(())
+        }
+    }
+
+    struct FakeQuadFormatter {
+        out: Vec<String>,
+    }
+
+    impl QuadsFormatter for FakeQuadFormatter {
+        type Error = Error;
+
+        fn format(&mut self, quad: &rio_api::model::Quad) -> StdResult<(), Self::Error> {
+            self.out.push(format!("{:?}", quad));
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn term2rio_converts_iri_bnode_and_literals() {
+        let mut buf = TermBuf::default();
+        let iri = BoxTerm::new_iri("http://example.org/foo").unwrap();
+        assert!(matches!(term2rio(&iri, &mut buf).unwrap(), Term::NamedNode(n) if n.iri == "http://example.org/foo"));
+
+        let bnode = BoxTerm::new_bnode("b1").unwrap();
+        assert!(matches!(term2rio(&bnode, &mut buf).unwrap(), Term::BlankNode(b) if b.id == "b1"));
+
+        let simple = BoxTerm::new_literal_dt("hello", xsd::string).unwrap();
+        assert!(matches!(
+            term2rio(&simple, &mut buf).unwrap(),
+            Term::Literal(Literal::Simple { value: "hello" })
+        ));
+
+        let lang = BoxTerm::new_literal_lang("hello", "en").unwrap();
+        assert!(matches!(
+            term2rio(&lang, &mut buf).unwrap(),
+            Term::Literal(Literal::LanguageTaggedString { value: "hello", language: "en" })
+        ));
+
+        let typed = BoxTerm::new_literal_dt("42", xsd::integer).unwrap();
+        assert!(matches!(
+            term2rio(&typed, &mut buf).unwrap(),
+            Term::Literal(Literal::Typed { value: "42", .. })
+        ));
+    }
+
+    #[test]
+    fn term2rio_rejects_variable() {
+        let mut buf = TermBuf::default();
+        let var = BoxTerm::new_variable("x").unwrap();
+        assert!(term2rio(&var, &mut buf).is_err());
+    }
+
+    #[test]
+    fn term2rio_subject_rejects_literal() {
+        let mut buf = TermBuf::default();
+        let lit = BoxTerm::new_literal_dt("hello", xsd::string).unwrap();
+        assert!(term2rio_subject(&lit, &mut buf).is_err());
+    }
+
+    #[test]
+    fn term2rio_graphname_rejects_variable() {
+        let mut buf = TermBuf::default();
+        let var = BoxTerm::new_variable("x").unwrap();
+        assert!(term2rio_graphname(&var, &mut buf).is_err());
+    }
+
+    #[test]
+    fn triple_sink_feeds_formatter_then_finishes() {
+        let mut sink = RioTripleSink::new(FakeTripleFormatter { out: Vec::new() });
+        let triple = [
+            BoxTerm::new_iri("http://example.org/s").unwrap(),
+            BoxTerm::new_iri("http://example.org/p").unwrap(),
+            BoxTerm::new_literal_dt("o", xsd::string).unwrap(),
+        ];
+        sink.feed(&triple).unwrap();
+        let formatter = sink.finish().unwrap();
+        assert_eq!(formatter.out.len(), 1);
+    }
+
+    #[test]
+    fn triple_sink_errors_after_finish() {
+        let mut sink = RioTripleSink::new(FakeTripleFormatter { out: Vec::new() });
+        sink.finish().unwrap();
+        assert!(sink.finish().is_err());
+    }
+
+    #[test]
+    fn quad_sink_feeds_formatter_then_finishes() {
+        let mut sink = RioQuadSink::new(FakeQuadFormatter { out: Vec::new() });
+        let quad = (
+            [
+                BoxTerm::new_iri("http://example.org/s").unwrap(),
+                BoxTerm::new_iri("http://example.org/p").unwrap(),
+                BoxTerm::new_literal_dt("o", xsd::string).unwrap(),
+            ],
+            Some(BoxTerm::new_iri("http://example.org/g").unwrap()),
+        );
+        sink.feed(&quad).unwrap();
+        let formatter = sink.finish().unwrap();
+        assert_eq!(formatter.out.len(), 1);
+    }
+
+    #[test]
+    fn quad_sink_errors_after_finish() {
+        let mut sink = RioQuadSink::new(FakeQuadFormatter { out: Vec::new() });
+        sink.finish().unwrap();
+        assert!(sink.finish().is_err());
+    }
+
+    struct MockTriplesParser {
+        items: std::collections::VecDeque<(String, String, String)>,
+    }
+
+    impl MockTriplesParser {
+        fn new(items: &[(&str, &str, &str)]) -> Self {
+            MockTriplesParser {
+                items: items
+                    .iter()
+                    .map(|(s, p, o)| (s.to_string(), p.to_string(), o.to_string()))
+                    .collect(),
+            }
+        }
+    }
+
+    impl TriplesParser for MockTriplesParser {
+        type Error = Error;
+
+        fn parse_step<E: From<Self::Error>>(
+            &mut self,
+            on_triple: &mut impl FnMut(rio_api::model::Triple) -> StdResult<(), E>,
+        ) -> StdResult<(), E> {
+            if let Some((s, p, o)) = self.items.pop_front() {
+                on_triple(rio_api::model::Triple {
+                    subject: Subject::NamedNode(NamedNode { iri: &s }),
+                    predicate: NamedNode { iri: &p },
+                    object: Term::Literal(Literal::Simple { value: &o }),
+                })?;
+            }
+            Ok(())
+        }
+
+        fn is_end(&self) -> bool {
+            self.items.is_empty()
+        }
+    }
+
+    impl QuadsParser for MockTriplesParser {
+        type Error = Error;
+
+        fn parse_step<E: From<Self::Error>>(
+            &mut self,
+            on_quad: &mut impl FnMut(rio_api::model::Quad) -> StdResult<(), E>,
+        ) -> StdResult<(), E> {
+            if let Some((s, p, o)) = self.items.pop_front() {
+                on_quad(rio_api::model::Quad {
+                    subject: Subject::NamedNode(NamedNode { iri: &s }),
+                    predicate: NamedNode { iri: &p },
+                    object: Term::Literal(Literal::Simple { value: &o }),
+                    graph_name: None,
+                })?;
+            }
+            Ok(())
+        }
+
+        fn is_end(&self) -> bool {
+            self.items.is_empty()
+        }
+    }
+
+    #[test]
+    fn source_iter_yields_triples_one_step_at_a_time() {
+        let parser = MockTriplesParser::new(&[
+            ("http://a/s1", "http://a/p", "v1"),
+            ("http://a/s2", "http://a/p", "v2"),
+        ]);
+        let source: RioSource<MockTriplesParser, Error> = Ok(parser).into();
+        let triples: Vec<_> = source.into_iter().collect::<Result<Vec<_>>>().unwrap();
+        assert_eq!(triples.len(), 2);
+        assert_eq!(&*triples[0][0].value(), "http://a/s1");
+        assert_eq!(&*triples[1][0].value(), "http://a/s2");
+    }
+
+    #[test]
+    fn source_iter_stops_without_draining_remaining_input() {
+        let parser = MockTriplesParser::new(&[
+            ("http://a/s1", "http://a/p", "v1"),
+            ("http://a/s2", "http://a/p", "v2"),
+            ("http://a/s3", "http://a/p", "v3"),
+        ]);
+        let source: RioSource<MockTriplesParser, Error> = Ok(parser).into();
+        let mut iter = source.into_iter();
+        let first = iter.next().unwrap().unwrap();
+        assert_eq!(&*first[0].value(), "http://a/s1");
+        match &iter.source {
+            RioSource::Parser(parser, _) => assert_eq!(parser.items.len(), 2),
+            RioSource::Error(_) => panic!("expected RioSource::Parser"),
+        }
+    }
+
+    #[test]
+    fn quad_source_iter_yields_quads_one_step_at_a_time() {
+        let parser = MockTriplesParser::new(&[
+            ("http://a/s1", "http://a/p", "v1"),
+            ("http://a/s2", "http://a/p", "v2"),
+        ]);
+        let source: RioSource<MockTriplesParser, Error> = Ok(parser).into();
+        let quads: Vec<_> = source.into_quad_iter().collect::<Result<Vec<_>>>().unwrap();
+        assert_eq!(quads.len(), 2);
+        assert_eq!(&*quads[0].0[0].value(), "http://a/s1");
+        assert!(quads[0].1.is_none());
+    }
+
+    /// A parser that always yields a single triple whose object has a
+    /// relative (non-absolute) datatype IRI, so that converting it via
+    /// `rio2refterm` fails.
+    struct MockFailingTriplesParser {
+        done: bool,
+    }
+
+    impl TriplesParser for MockFailingTriplesParser {
+        type Error = Error;
+
+        fn parse_step<E: From<Self::Error>>(
+            &mut self,
+            on_triple: &mut impl FnMut(rio_api::model::Triple) -> StdResult<(), E>,
+        ) -> StdResult<(), E> {
+            if !self.done {
+                self.done = true;
+                on_triple(rio_api::model::Triple {
+                    subject: Subject::NamedNode(NamedNode { iri: "http://a/s" }),
+                    predicate: NamedNode { iri: "http://a/p" },
+                    object: Term::Literal(Literal::Typed {
+                        value: "o",
+                        datatype: NamedNode { iri: "relative-datatype" },
+                    }),
+                })?;
+            }
+            Ok(())
+        }
+
+        fn is_end(&self) -> bool {
+            self.done
+        }
+    }
+
+    struct CollectingSink {
+        count: usize,
+    }
+
+    impl TripleSink for CollectingSink {
+        type Outcome = usize;
+        type Error = Error;
+
+        fn feed<T: Triple>(&mut self, _t: &T) -> StdResult<(), Self::Error> {
+            self.count += 1;
+            Ok(())
+        }
+
+        fn finish(&mut self) -> StdResult<Self::Outcome, Self::Error> {
+            Ok(self.count)
+        }
+    }
+
+    #[test]
+    fn in_sink_propagates_conversion_error_instead_of_panicking() {
+        let parser = MockFailingTriplesParser { done: false };
+        let mut source: RioSource<MockFailingTriplesParser, Error> = Ok(parser).into();
+        let mut sink = CollectingSink { count: 0 };
+        assert!(source.in_sink(&mut sink).is_err());
+    }
+
+    #[test]
+    fn source_iter_propagates_conversion_error_instead_of_panicking() {
+        let parser = MockFailingTriplesParser { done: false };
+        let source: RioSource<MockFailingTriplesParser, Error> = Ok(parser).into();
+        let results: Vec<_> = source.into_iter().collect();
+        assert_eq!(results.len(), 1);
+        assert!(results[0].is_err());
+    }
+
+    /// A parser that always yields a single triple whose object is a
+    /// language-tagged literal with an invalid BCP47 tag, so that converting
+    /// it via `rio2refterm` fails.
+    struct MockFailingLangTagTriplesParser {
+        done: bool,
+    }
+
+    impl TriplesParser for MockFailingLangTagTriplesParser {
+        type Error = Error;
+
+        fn parse_step<E: From<Self::Error>>(
+            &mut self,
+            on_triple: &mut impl FnMut(rio_api::model::Triple) -> StdResult<(), E>,
+        ) -> StdResult<(), E> {
+            if !self.done {
+                self.done = true;
+                on_triple(rio_api::model::Triple {
+                    subject: Subject::NamedNode(NamedNode { iri: "http://a/s" }),
+                    predicate: NamedNode { iri: "http://a/p" },
+                    object: Term::Literal(Literal::LanguageTaggedString {
+                        value: "o",
+                        language: "not a lang!!",
+                    }),
+                })?;
+            }
+            Ok(())
+        }
+
+        fn is_end(&self) -> bool {
+            self.done
+        }
+    }
+
+    #[test]
+    fn in_sink_propagates_invalid_lang_tag_error_instead_of_panicking() {
+        let parser = MockFailingLangTagTriplesParser { done: false };
+        let mut source: RioSource<MockFailingLangTagTriplesParser, Error> = Ok(parser).into();
+        let mut sink = CollectingSink { count: 0 };
+        assert!(source.in_sink(&mut sink).is_err());
+    }
+
+    #[test]
+    fn source_iter_propagates_invalid_lang_tag_error_instead_of_panicking() {
+        let parser = MockFailingLangTagTriplesParser { done: false };
+        let source: RioSource<MockFailingLangTagTriplesParser, Error> = Ok(parser).into();
+        let results: Vec<_> = source.into_iter().collect();
+        assert_eq!(results.len(), 1);
+        assert!(results[0].is_err());
+    }
+
+    #[test]
+    fn resolve_iri_leaves_absolute_iri_untouched() {
+        let resolved = resolve_iri("http://example.org/foo", None).unwrap();
+        assert_eq!(&*resolved.value(), "http://example.org/foo");
+    }
+
+    #[test]
+    fn resolve_iri_joins_relative_iri_against_base() {
+        let base = Iri::<Box<str>>::new("http://example.org/a/b".to_string()).unwrap();
+        let resolved = resolve_iri("c", Some(&base)).unwrap();
+        assert!(resolved.is_absolute());
+        assert_eq!(&*resolved.value(), "http://example.org/a/c");
+    }
+
+    #[test]
+    fn resolve_iri_without_base_errors() {
+        assert!(resolve_iri("relative", None).is_err());
+    }
+
+    #[test]
+    fn with_base_accepts_absolute_iri() {
+        let source: RioSource<(), Error> = RioSource::Parser((), None);
+        let source = source.with_base("http://example.org/").unwrap();
+        match source {
+            RioSource::Parser(_, Some(base)) => assert_eq!(&*base.value(), "http://example.org/"),
+            _ => panic!("expected a configured base"),
+        }
+    }
+
+    #[test]
+    fn with_base_rejects_relative_iri() {
+        let source: RioSource<(), Error> = RioSource::Parser((), None);
+        assert!(source.with_base("not/absolute").is_err());
+    }
+
+    #[test]
+    fn with_base_is_noop_on_already_failed_source() {
+        let source: RioSource<(), Error> = RioSource::Error(Some(already_finished()));
+        assert!(source.with_base("http://example.org/").is_ok());
+    }
 }
\ No newline at end of file
@@ -48,8 +48,9 @@
 
 use mownstr::MownStr;
 use std::convert::TryInto;
-use std::fmt::Debug;
+use std::fmt::{self, Debug};
 use std::hash::Hash;
+use std::io;
 use std::rc::Rc;
 use std::sync::Arc;
 
@@ -379,6 +380,7 @@ where
     /// # Pre-condition
     ///
     /// This function requires that `name` is a valid variable name.
+    /// In debug mode this constraint is asserted.
     pub fn new_variable_unchecked<U>(name: U) -> Term<T>
     where
         U: AsRef<str>,
@@ -414,6 +416,77 @@ where
             _ => true,
         }
     }
+
+    /// Write this term's canonical NTriples representation to `w`.
+    ///
+    /// IRIs are written as `<...>` (with their full, non-prefixed text),
+    /// blank nodes as `_:id`, literals as `"value"` (with `"`, `\`, `\n`,
+    /// `\r` and `\t` escaped) suffixed by `^^<datatype-iri>` (omitted when
+    /// the datatype is `xsd:string`) or `@lang`, and variables as `?name`.
+    pub fn write_nt<W>(&self, w: &mut W) -> fmt::Result
+    where
+        W: fmt::Write,
+    {
+        use self::Term::*;
+
+        match self {
+            Iri(iri) => write!(w, "<{}>", iri.value()),
+            BNode(bn) => write!(w, "_:{}", bn.value()),
+            Literal(lit) => {
+                w.write_char('"')?;
+                write_nt_escaped(w, &lit.value())?;
+                w.write_char('"')?;
+                match lit.lang() {
+                    Some(lang) => write!(w, "@{}", lang),
+                    None if lit.dt().value().as_ref() == crate::ns::xsd::string => Ok(()),
+                    None => write!(w, "^^<{}>", lit.dt().value()),
+                }
+            }
+            Variable(var) => write!(w, "?{}", var.value()),
+        }
+    }
+
+    /// Write this term's canonical N3 representation to `w`.
+    ///
+    /// For a single term, this coincides with [`write_nt`](#method.write_nt);
+    /// N3-specific syntax (prefixed names, collections...) only comes into
+    /// play when serializing a full graph.
+    pub fn write_n3<W>(&self, w: &mut W) -> io::Result<()>
+    where
+        W: io::Write,
+    {
+        let mut buf = String::new();
+        self.write_nt(&mut buf)
+            .expect("fmt::Write can not fail on a String");
+        w.write_all(buf.as_bytes())
+    }
+
+    /// Return this term's canonical N3 representation as a `String`.
+    ///
+    /// Convenient for debugging, hashing, or building line-oriented output
+    /// without pulling in a full serializer.
+    pub fn n3(&self) -> String {
+        let mut buf = String::new();
+        self.write_nt(&mut buf)
+            .expect("fmt::Write can not fail on a String");
+        buf
+    }
+}
+
+/// Write `txt` to `w`, escaping `"`, `\`, `\n`, `\r` and `\t`
+/// as required by the NTriples/N3 literal grammar.
+fn write_nt_escaped<W: fmt::Write>(w: &mut W, txt: &str) -> fmt::Result {
+    for c in txt.chars() {
+        match c {
+            '"' => w.write_str("\\\"")?,
+            '\\' => w.write_str("\\\\")?,
+            '\n' => w.write_str("\\n")?,
+            '\r' => w.write_str("\\r")?,
+            '\t' => w.write_str("\\t")?,
+            c => w.write_char(c)?,
+        }
+    }
+    Ok(())
 }
 
 impl<T, U> PartialEq<Term<U>> for Term<T>
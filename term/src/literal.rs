@@ -0,0 +1,203 @@
+//! I define [`Literal`](enum.Literal.html),
+//! the internal representation of RDF literals.
+
+use mownstr::MownStr;
+
+use super::iri::{Iri, Normalization};
+use super::{Result, TermData, TermError};
+
+/// Internal representation of an RDF literal.
+#[derive(Clone, Copy, Debug, Eq)]
+pub enum Literal<TD>
+where
+    TD: TermData,
+{
+    /// A literal with an explicit datatype.
+    Dt(TD, Iri<TD>),
+    /// A language-tagged literal (implicitly typed as `xsd:string`).
+    Lang(TD, TD),
+}
+
+impl<TD> Literal<TD>
+where
+    TD: TermData,
+{
+    /// Build a new literal with the given value and language tag.
+    ///
+    /// May fail if `lang` is not a valid BCP47 language tag.
+    pub fn new_lang<U, V>(txt: U, lang: V) -> Result<Self>
+    where
+        V: AsRef<str>,
+        TD: From<U> + From<V>,
+    {
+        check_language_tag(lang.as_ref())?;
+        Ok(Literal::Lang(TD::from(txt), TD::from(lang)))
+    }
+
+    /// Build a new literal with the given value and language tag,
+    /// without checking that the language tag is valid.
+    ///
+    /// # Pre-condition
+    ///
+    /// This function requires that `lang` is a valid BCP47 language tag.
+    /// In debug mode this constraint is asserted.
+    pub fn new_lang_unchecked<U, V>(txt: U, lang: V) -> Self
+    where
+        V: AsRef<str>,
+        TD: From<U> + From<V>,
+    {
+        debug_assert!(check_language_tag(lang.as_ref()).is_ok());
+        Literal::Lang(TD::from(txt), TD::from(lang))
+    }
+
+    /// Build a new literal with the given value and datatype.
+    pub fn new_dt<U>(txt: U, dt: Iri<TD>) -> Self
+    where
+        TD: From<U>,
+    {
+        Literal::Dt(TD::from(txt), dt)
+    }
+
+    /// Borrow the inner contents of the literal.
+    pub fn as_ref(&self) -> Literal<&TD> {
+        match self {
+            Literal::Dt(txt, dt) => Literal::Dt(txt, dt.as_ref()),
+            Literal::Lang(txt, lang) => Literal::Lang(txt, lang),
+        }
+    }
+
+    /// Borrow the inner contents of the literal as `&str`.
+    pub fn as_ref_str(&self) -> Literal<&str> {
+        match self {
+            Literal::Dt(txt, dt) => Literal::Dt(txt.as_ref(), dt.as_ref_str()),
+            Literal::Lang(txt, lang) => Literal::Lang(txt.as_ref(), lang.as_ref()),
+        }
+    }
+
+    /// Create a new literal by applying `f` to the `TermData` of `self`.
+    pub fn map<F, TD2>(self, mut f: F) -> Literal<TD2>
+    where
+        F: FnMut(TD) -> TD2,
+        TD2: TermData,
+    {
+        match self {
+            Literal::Dt(txt, dt) => Literal::Dt(f(txt), dt.map(f)),
+            Literal::Lang(txt, lang) => Literal::Lang(f(txt), f(lang)),
+        }
+    }
+
+    /// Clone self while transforming the inner `TermData` with the given factory.
+    pub fn clone_map<'a, U, F>(&'a self, mut factory: F) -> Literal<U>
+    where
+        U: TermData,
+        F: FnMut(&'a str) -> U,
+    {
+        match self {
+            Literal::Dt(txt, dt) => Literal::Dt(factory(txt.as_ref()), dt.clone_map(factory)),
+            Literal::Lang(txt, lang) => Literal::Lang(factory(txt.as_ref()), factory(lang.as_ref())),
+        }
+    }
+
+    /// Return a literal equivalent to this one, with its datatype IRI (if any)
+    /// normalized according to `policy`.
+    pub fn normalized(&self, policy: Normalization) -> Literal<MownStr> {
+        match self {
+            Literal::Dt(txt, dt) => Literal::Dt(txt.as_ref().into(), dt.normalized(policy)),
+            Literal::Lang(txt, lang) => Literal::Lang(txt.as_ref().into(), lang.as_ref().into()),
+        }
+    }
+
+    /// Return this literal's lexical value
+    /// (*not* its datatype or language tag).
+    pub fn value(&self) -> MownStr {
+        match self {
+            Literal::Dt(txt, _) => txt.as_ref().into(),
+            Literal::Lang(txt, _) => txt.as_ref().into(),
+        }
+    }
+
+    /// Return this literal's datatype IRI.
+    ///
+    /// Language-tagged literals are always implicitly typed as `xsd:string`.
+    pub fn dt(&self) -> Iri<&str> {
+        match self {
+            Literal::Dt(_, dt) => dt.as_ref_str(),
+            Literal::Lang(..) => Iri::new_unchecked(super::ns::xsd::string),
+        }
+    }
+
+    /// Return this literal's language tag, if any.
+    pub fn lang(&self) -> Option<&str> {
+        match self {
+            Literal::Dt(..) => None,
+            Literal::Lang(_, lang) => Some(lang.as_ref()),
+        }
+    }
+
+    /// Return whether this literal is absolute,
+    /// i.e. whether its datatype IRI (if any) is absolute.
+    pub fn is_absolute(&self) -> bool {
+        match self {
+            Literal::Dt(_, dt) => dt.is_absolute(),
+            Literal::Lang(..) => true,
+        }
+    }
+}
+
+impl<T, U> PartialEq<Literal<U>> for Literal<T>
+where
+    T: TermData,
+    U: TermData,
+{
+    fn eq(&self, other: &Literal<U>) -> bool {
+        match (self, other) {
+            (Literal::Dt(txt1, dt1), Literal::Dt(txt2, dt2)) => {
+                txt1.as_ref() == txt2.as_ref() && dt1 == dt2
+            }
+            (Literal::Lang(txt1, lang1), Literal::Lang(txt2, lang2)) => {
+                txt1.as_ref() == txt2.as_ref() && lang1.as_ref() == lang2.as_ref()
+            }
+            _ => false,
+        }
+    }
+}
+
+/// Check that `tag` is a syntactically valid BCP47 language tag,
+/// i.e. a non-empty sequence of `-`-separated alphanumeric subtags,
+/// each 1 to 8 characters long, whose first (primary language) subtag
+/// is purely alphabetic.
+fn check_language_tag(tag: &str) -> Result<()> {
+    let mut subtags = tag.split('-');
+    match subtags.next() {
+        Some(primary) if !primary.is_empty() && primary.len() <= 8 && primary.chars().all(|c| c.is_ascii_alphabetic()) => {}
+        _ => return Err(TermError::InvalidLanguageTag(tag.to_string())),
+    }
+    for subtag in subtags {
+        if subtag.is_empty() || subtag.len() > 8 || !subtag.chars().all(|c| c.is_ascii_alphanumeric()) {
+            return Err(TermError::InvalidLanguageTag(tag.to_string()));
+        }
+    }
+    Ok(())
+}
+
+/// Types that can be turned into a simple (`xsd:string`-typed) literal `Term`.
+///
+/// This is used to implement `From<&str>` and `From<String>` for `Term`.
+pub trait AsLiteral {
+    /// Build a simple literal term from this value.
+    fn as_term<TD>(&self) -> super::Term<TD>
+    where
+        TD: TermData + for<'a> From<&'a str>;
+}
+
+impl<S> AsLiteral for S
+where
+    S: AsRef<str>,
+{
+    fn as_term<TD>(&self) -> super::Term<TD>
+    where
+        TD: TermData + for<'a> From<&'a str>,
+    {
+        Literal::Dt(TD::from(self.as_ref()), Iri::new_unchecked(super::ns::xsd::string)).into()
+    }
+}
@@ -0,0 +1,98 @@
+//! Unit tests for the term crate.
+
+use super::*;
+
+#[test]
+fn new_variable_accepts_valid_names() {
+    assert!(StaticTerm::new_variable("x").is_ok());
+    assert!(StaticTerm::new_variable("_foo").is_ok());
+    assert!(StaticTerm::new_variable("x1").is_ok());
+    assert!(StaticTerm::new_variable("1x").is_ok());
+    assert!(StaticTerm::new_variable("a\u{B7}b").is_ok());
+}
+
+#[test]
+fn new_variable_rejects_invalid_names() {
+    assert!(StaticTerm::new_variable("").is_err());
+    assert!(StaticTerm::new_variable("has space").is_err());
+    assert!(StaticTerm::new_variable("has-dash").is_err());
+    assert!(StaticTerm::new_variable("\u{B7}leading").is_err());
+}
+
+#[test]
+fn write_nt_iri() {
+    let t = StaticTerm::new_iri("http://example.org/foo").unwrap();
+    assert_eq!(t.n3(), "<http://example.org/foo>");
+}
+
+#[test]
+fn write_nt_bnode() {
+    let t = StaticTerm::new_bnode("b1").unwrap();
+    assert_eq!(t.n3(), "_:b1");
+}
+
+#[test]
+fn write_nt_variable() {
+    let t = StaticTerm::new_variable("x").unwrap();
+    assert_eq!(t.n3(), "?x");
+}
+
+#[test]
+fn write_nt_literal_xsd_string_has_no_suffix() {
+    let t = StaticTerm::new_literal_dt("hello", ns::xsd::string).unwrap();
+    assert_eq!(t.n3(), "\"hello\"");
+}
+
+#[test]
+fn write_nt_literal_other_datatype_has_suffix() {
+    let t = StaticTerm::new_literal_dt("42", ns::xsd::integer).unwrap();
+    assert_eq!(t.n3(), "\"42\"^^<http://www.w3.org/2001/XMLSchema#integer>");
+}
+
+#[test]
+fn write_nt_literal_lang() {
+    let t = StaticTerm::new_literal_lang("hello", "en").unwrap();
+    assert_eq!(t.n3(), "\"hello\"@en");
+}
+
+#[test]
+fn write_nt_literal_escapes_special_chars() {
+    let raw = "a\"b\\c\nd\re\tf";
+    let t = StaticTerm::new_literal_dt(raw, ns::xsd::string).unwrap();
+    let mut expected = String::from("\"");
+    for c in raw.chars() {
+        match c {
+            '"' => expected.push_str("\\\""),
+            '\\' => expected.push_str("\\\\"),
+            '\n' => expected.push_str("\\n"),
+            '\r' => expected.push_str("\\r"),
+            '\t' => expected.push_str("\\t"),
+            c => expected.push(c),
+        }
+    }
+    expected.push('"');
+    assert_eq!(t.n3(), expected);
+}
+
+#[test]
+fn write_n3_matches_n3() {
+    let t = StaticTerm::new_iri("http://example.org/foo").unwrap();
+    let mut buf = Vec::new();
+    t.write_n3(&mut buf).unwrap();
+    assert_eq!(String::from_utf8(buf).unwrap(), t.n3());
+}
+
+#[test]
+fn new_literal_lang_accepts_valid_bcp47_tags() {
+    assert!(StaticTerm::new_literal_lang("x", "en").is_ok());
+    assert!(StaticTerm::new_literal_lang("x", "en-US").is_ok());
+    assert!(StaticTerm::new_literal_lang("x", "zh-Hans-CN").is_ok());
+}
+
+#[test]
+fn new_literal_lang_rejects_invalid_bcp47_tags() {
+    assert!(StaticTerm::new_literal_lang("x", "not a lang!!").is_err());
+    assert!(StaticTerm::new_literal_lang("x", "").is_err());
+    assert!(StaticTerm::new_literal_lang("x", "-en").is_err());
+    assert!(StaticTerm::new_literal_lang("x", "toolongsubtag-en").is_err());
+}
@@ -0,0 +1,139 @@
+//! I define [`Variable`](struct.Variable.html),
+//! the internal representation of SPARQL/Notation3-style variables.
+
+use std::hash::{Hash, Hasher};
+
+use mownstr::MownStr;
+
+use super::{Result, TermData, TermError};
+
+/// Internal representation of a universally quantified variable,
+/// as used e.g. in SPARQL and Notation3.
+#[derive(Clone, Copy, Debug, Eq)]
+pub struct Variable<TD>(TD)
+where
+    TD: TermData;
+
+impl<TD> Variable<TD>
+where
+    TD: TermData,
+{
+    /// Return a new variable with the given name.
+    ///
+    /// May fail if `name` is not a valid variable name,
+    /// as per the SPARQL `VARNAME` production.
+    pub fn new<U>(name: U) -> Result<Variable<TD>>
+    where
+        U: AsRef<str>,
+        TD: From<U>,
+    {
+        check_varname(name.as_ref())?;
+        Ok(Variable(TD::from(name)))
+    }
+
+    /// Return a new variable with the given name, without checking it.
+    ///
+    /// # Pre-condition
+    ///
+    /// This function requires that `name` is a valid variable name.
+    /// In debug mode this constraint is asserted.
+    pub fn new_unchecked<U>(name: U) -> Variable<TD>
+    where
+        U: AsRef<str>,
+        TD: From<U>,
+    {
+        debug_assert!(check_varname(name.as_ref()).is_ok());
+        Variable(TD::from(name))
+    }
+
+    /// Borrow the inner contents of the variable.
+    pub fn as_ref(&self) -> Variable<&TD> {
+        Variable(&self.0)
+    }
+
+    /// Borrow the inner contents of the variable as `&str`.
+    pub fn as_ref_str(&self) -> Variable<&str> {
+        Variable(self.0.as_ref())
+    }
+
+    /// Create a new variable by applying `f` to the `TermData` of `self`.
+    pub fn map<F, TD2>(self, mut f: F) -> Variable<TD2>
+    where
+        F: FnMut(TD) -> TD2,
+        TD2: TermData,
+    {
+        Variable(f(self.0))
+    }
+
+    /// Clone self while transforming the inner `TermData` with the given factory.
+    pub fn clone_map<'a, U, F>(&'a self, mut factory: F) -> Variable<U>
+    where
+        U: TermData,
+        F: FnMut(&'a str) -> U,
+    {
+        Variable(factory(self.0.as_ref()))
+    }
+
+    /// Return this variable's name.
+    pub fn value(&self) -> MownStr {
+        self.0.as_ref().into()
+    }
+}
+
+impl<T, U> PartialEq<Variable<U>> for Variable<T>
+where
+    T: TermData,
+    U: TermData,
+{
+    fn eq(&self, other: &Variable<U>) -> bool {
+        self.0.as_ref() == other.0.as_ref()
+    }
+}
+
+impl<TD> Hash for Variable<TD>
+where
+    TD: TermData,
+{
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.0.as_ref().hash(state)
+    }
+}
+
+/// Check that `name` is a valid SPARQL/Notation3 variable name,
+/// i.e. that it matches the SPARQL `VARNAME` production.
+fn check_varname(name: &str) -> Result<()> {
+    let mut chars = name.chars();
+    match chars.next() {
+        Some(c) if is_pn_chars_u(c) || c.is_ascii_digit() => {}
+        _ => return Err(TermError::InvalidVariableName(name.to_string())),
+    }
+    for c in chars {
+        let ok = is_pn_chars_u(c)
+            || c.is_ascii_digit()
+            || c == '\u{B7}'
+            || ('\u{300}'..='\u{36F}').contains(&c)
+            || ('\u{203F}'..='\u{2040}').contains(&c);
+        if !ok {
+            return Err(TermError::InvalidVariableName(name.to_string()));
+        }
+    }
+    Ok(())
+}
+
+/// The `PN_CHARS_U` production: `PN_CHARS_BASE | '_'`.
+fn is_pn_chars_u(c: char) -> bool {
+    c == '_'
+        || c.is_ascii_alphabetic()
+        || ('\u{C0}'..='\u{D6}').contains(&c)
+        || ('\u{D8}'..='\u{F6}').contains(&c)
+        || ('\u{F8}'..='\u{2FF}').contains(&c)
+        || ('\u{370}'..='\u{37D}').contains(&c)
+        || ('\u{37F}'..='\u{1FFF}').contains(&c)
+        || ('\u{200C}'..='\u{200D}').contains(&c)
+        || ('\u{2070}'..='\u{218F}').contains(&c)
+        || ('\u{2C00}'..='\u{2FEF}').contains(&c)
+        || ('\u{3001}'..='\u{D7FF}').contains(&c)
+        || ('\u{F900}'..='\u{FDCF}').contains(&c)
+        || ('\u{FDF0}'..='\u{FFFD}').contains(&c)
+        || ('\u{10000}'..='\u{EFFFF}').contains(&c)
+}